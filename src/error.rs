@@ -0,0 +1,125 @@
+//! Errors produced by the fallible `FluentString` combinators, plus
+//! the boundary-checking helpers behind the `f_try_*` ones.
+use core::fmt;
+#[cfg(any(feature = "std", feature = "heapless"))]
+use core::ops::{Bound, RangeBounds};
+
+/// Errors returned by the `f_try_*` methods on
+/// [`FluentString`](crate::FluentString), and on fixed-capacity
+/// backends by `f_push_fmt`, `f_extend_chars`, `f_extend_str` and
+/// `f_push_joined`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FluentError {
+    /// `idx` (or the end of a range) was greater than the string's length.
+    OutOfBounds {
+        /// The offending index.
+        idx: usize,
+        /// The length it was checked against.
+        len: usize,
+    },
+    /// `idx` fell inside a multi-byte UTF-8 sequence.
+    NotCharBoundary {
+        /// The offending index.
+        idx: usize,
+    },
+    /// The operation would have exceeded a fixed-capacity backend's capacity.
+    CapacityExceeded {
+        /// The length in bytes of the piece whose push overflowed.
+        additional: usize,
+        /// The backend's total capacity.
+        capacity: usize,
+    },
+    /// A `core::fmt::Write` operation failed. `core::fmt::Error`
+    /// carries no detail about the cause, so on fixed-capacity
+    /// backends this can also mean the write ran out of capacity.
+    Format,
+    /// A range's start was greater than its end, even though both
+    /// were individually in bounds.
+    InvalidRange {
+        /// The range's resolved start.
+        start: usize,
+        /// The range's resolved end.
+        end: usize,
+    },
+}
+
+impl fmt::Display for FluentError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FluentError::OutOfBounds { idx, len } => {
+                write!(f, "index {idx} is out of bounds for a string of length {len}")
+            }
+            FluentError::NotCharBoundary { idx } => {
+                write!(f, "index {idx} is not a UTF-8 char boundary")
+            }
+            FluentError::CapacityExceeded { additional, capacity } => {
+                write!(
+                    f,
+                    "operation needs {additional} more byte(s) but capacity is {capacity}"
+                )
+            }
+            FluentError::Format => write!(f, "formatting failed"),
+            FluentError::InvalidRange { start, end } => {
+                write!(f, "range start {start} is greater than range end {end}")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for FluentError {}
+
+/// Returns `true` if `idx` is a valid boundary, i.e. `idx <= len`.
+#[must_use]
+pub fn is_inside_boundary(idx: usize, len: usize) -> bool {
+    idx <= len
+}
+
+/// Returns `true` if byte index `idx` falls on a UTF-8 char boundary
+/// of `s`. Mirrors the check `arraystring` and similar fixed-capacity
+/// string crates use: always true at `0` and at `s.len()`, otherwise
+/// the byte at `idx` must not be a UTF-8 continuation byte.
+#[must_use]
+pub fn is_char_boundary(s: &str, idx: usize) -> bool {
+    if idx == 0 || idx == s.len() {
+        return true;
+    }
+    match s.as_bytes().get(idx) {
+        Some(byte) => byte & 0xC0 != 0x80,
+        None => false,
+    }
+}
+
+/// Resolves `range` against `len`, checking that both endpoints are
+/// in bounds and that the range isn't inverted (char-boundary
+/// checking is left to the caller, which has the `&str` needed to do
+/// it).
+///
+/// Only `std_impl` and `heapless_impl` call this, so with neither
+/// backend feature enabled it would otherwise be dead code.
+#[cfg(any(feature = "std", feature = "heapless"))]
+pub(crate) fn resolve_range<R>(range: &R, len: usize) -> Result<(usize, usize), FluentError>
+where
+    R: RangeBounds<usize>,
+{
+    let start = match range.start_bound() {
+        Bound::Included(&n) => n,
+        Bound::Excluded(&n) => n.checked_add(1).unwrap_or_else(|| len.saturating_add(1)),
+        Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(&n) => n.checked_add(1).unwrap_or_else(|| len.saturating_add(1)),
+        Bound::Excluded(&n) => n,
+        Bound::Unbounded => len,
+    };
+    if !is_inside_boundary(start, len) {
+        return Err(FluentError::OutOfBounds { idx: start, len });
+    }
+    if !is_inside_boundary(end, len) {
+        return Err(FluentError::OutOfBounds { idx: end, len });
+    }
+    if start > end {
+        return Err(FluentError::InvalidRange { start, end });
+    }
+    Ok((start, end))
+}
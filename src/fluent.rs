@@ -0,0 +1,161 @@
+//! The core, backend-agnostic `FluentString` trait.
+//!
+//! Everything in this module is `no_std`-compatible: the default
+//! combinators (`f_push_if`, `f_push_str_if`, `f_truncate_if`) only
+//! need `Sized` plus the required methods below, so they work
+//! unmodified for every backend, heap-allocated or not.
+use crate::FluentError;
+use core::fmt;
+use core::ops::RangeBounds;
+
+pub trait FluentString: Sized {
+    /// The return type of the formatting and bulk-append combinators
+    /// below (`f_push_fmt`, `f_extend_chars`, `f_extend_str`,
+    /// `f_push_joined`).
+    ///
+    /// These operations are genuinely infallible on heap-backed
+    /// backends (`String`, `&mut String` set this to `Self`), but can
+    /// run out of room on fixed-capacity backends (which set this to
+    /// `Result<Self, FluentError>`). The associated type lets each
+    /// backend report the honest signature instead of forcing every
+    /// caller to unwrap a `Result` that heap backends can never fail.
+    type FallibleOutput;
+
+    /// As `String::clear` except returns `self`
+    #[must_use]
+    fn f_clear(self) -> Self;
+    /// As `String::insert` except returns `self`
+    ///
+    /// # Panics
+    /// Panics if `idx` is out of bounds or not on a UTF-8 char
+    /// boundary. Use `f_try_insert` to handle this without panicking.
+    #[must_use]
+    fn f_insert(self, idx: usize, ch: char) -> Self;
+    /// As `String::insert_str` except returns `self`
+    ///
+    /// # Panics
+    /// Panics if `idx` is out of bounds or not on a UTF-8 char
+    /// boundary. Use `f_try_insert_str` to handle this without panicking.
+    #[must_use]
+    fn f_insert_str(self, idx: usize, string: &str) -> Self;
+    /// As `String::push` except returns `self`
+    #[must_use]
+    fn f_push(self, ch: char) -> Self;
+    /// As `String::push_str` except returns `self`
+    #[must_use]
+    fn f_push_str(self, string: &str) -> Self;
+    /// As `String::replace_range` except returns `self`
+    ///
+    /// # Panics
+    /// Panics if the start or end of `range` is out of bounds or not
+    /// on a UTF-8 char boundary. Use `f_try_replace_range` to handle
+    /// this without panicking.
+    #[must_use]
+    fn f_replace_range<R>(self, range: R, replace_with: &str) -> Self
+    where
+        R: RangeBounds<usize>;
+    /// As `String::retain` except returns `self`
+    #[must_use]
+    fn f_retain<F>(self, f: F) -> Self
+    where
+        F: FnMut(char) -> bool;
+    /// As `String::truncate` except returns `self`
+    ///
+    /// # Panics
+    /// Panics if `new_len` is not on a UTF-8 char boundary. Use
+    /// `f_try_truncate` to handle this without panicking.
+    #[must_use]
+    fn f_truncate(self, new_len: usize) -> Self;
+
+    /// As `FluentString::f_insert` except returns `Err` instead of
+    /// panicking if `idx` is out of bounds or not on a char boundary.
+    /// # Errors
+    /// See [`FluentError`]
+    fn f_try_insert(self, idx: usize, ch: char) -> Result<Self, FluentError>;
+    /// As `FluentString::f_insert_str` except returns `Err` instead of
+    /// panicking if `idx` is out of bounds or not on a char boundary.
+    /// # Errors
+    /// See [`FluentError`]
+    fn f_try_insert_str(self, idx: usize, string: &str) -> Result<Self, FluentError>;
+    /// As `FluentString::f_truncate` except returns `Err` instead of
+    /// panicking if `new_len` is not on a char boundary.
+    /// # Errors
+    /// See [`FluentError`]
+    fn f_try_truncate(self, new_len: usize) -> Result<Self, FluentError>;
+    /// As `FluentString::f_replace_range` except returns `Err` instead
+    /// of panicking if either end of `range` is out of bounds or not
+    /// on a char boundary.
+    /// # Errors
+    /// See [`FluentError`]
+    fn f_try_replace_range<R>(self, range: R, replace_with: &str) -> Result<Self, FluentError>
+    where
+        R: RangeBounds<usize>;
+
+    /// Writes `args` directly onto the buffer, as `core::fmt::Write::write_fmt`
+    /// would, except returns `self` (wrapped in [`FallibleOutput`](Self::FallibleOutput))
+    /// on success. Prefer the [`f_write!`](crate::f_write) macro over
+    /// calling this directly; it builds the `Arguments` for you.
+    fn f_push_fmt(self, args: fmt::Arguments<'_>) -> Self::FallibleOutput;
+
+    /// Pushes every `char` in `iter` in order, as repeated
+    /// `FluentString::f_push` calls would. Infallible for heap-backed
+    /// backends; see [`FallibleOutput`](Self::FallibleOutput).
+    fn f_extend_chars<I>(self, iter: I) -> Self::FallibleOutput
+    where
+        I: IntoIterator<Item = char>;
+
+    /// Pushes every `&str` in `iter` in order, as repeated
+    /// `FluentString::f_push_str` calls would. Infallible for
+    /// heap-backed backends; see [`FallibleOutput`](Self::FallibleOutput).
+    fn f_extend_str<'a, I>(self, iter: I) -> Self::FallibleOutput
+    where
+        I: IntoIterator<Item = &'a str>;
+
+    /// Pushes every `&str` in `iter`, separated by `sep`. A `sep` is
+    /// emitted before every item except the first *relative to the
+    /// current buffer contents* — i.e. if `self` is already non-empty,
+    /// a leading `sep` is emitted before the first item too, matching
+    /// the existing `f_push_str_if` comma pattern. Infallible for
+    /// heap-backed backends; see [`FallibleOutput`](Self::FallibleOutput).
+    fn f_push_joined<'a, I>(self, sep: &str, iter: I) -> Self::FallibleOutput
+    where
+        I: IntoIterator<Item = &'a str>;
+
+    /// As `FluentString::f_push` except only if `f` returns true
+    #[must_use]
+    fn f_push_if<F>(self, ch: char, f: F) -> Self
+    where
+        F: Fn(&Self, char) -> bool,
+    {
+        if f(&self, ch) {
+            self.f_push(ch)
+        } else {
+            self
+        }
+    }
+
+    /// As `FluentString::f_push_str` except only if `f` returns true
+    #[must_use]
+    fn f_push_str_if<F>(self, string: &str, f: F) -> Self
+    where
+        F: Fn(&Self, &str) -> bool,
+    {
+        if f(&self, string) {
+            self.f_push_str(string)
+        } else {
+            self
+        }
+    }
+
+    /// As `FluentString::f_truncate` except only if `f` returns Some(usize)
+    #[must_use]
+    fn f_truncate_if<F>(self, f: F) -> Self
+    where
+        F: Fn(&Self) -> Option<usize>,
+    {
+        match f(&self) {
+            Some(l) => self.f_truncate(l),
+            None => self
+        }
+    }
+}
@@ -0,0 +1,170 @@
+//! Fluent implementation for `heapless::String<N>`, a fixed-capacity,
+//! `no_std` stack string.
+//!
+//! Capacity overflow panics in the methods that return `Self`, for
+//! parity with the `std` backend; the `f_try_*` methods and the
+//! formatting and bulk-append combinators return it as an `Err`
+//! instead. `f_insert`, `f_insert_str` and `f_replace_range` are thin
+//! wrappers around their `f_try_*` counterparts; `f_push`,
+//! `f_push_str`, `f_retain` and `f_truncate` call the `heapless`
+//! methods directly.
+use crate::error::resolve_range;
+use crate::{is_char_boundary, is_inside_boundary, FluentError, FluentString};
+use core::ops::RangeBounds;
+use heapless::String as HeaplessString;
+
+impl<const N: usize> FluentString for HeaplessString<N> {
+    type FallibleOutput = Result<Self, FluentError>;
+
+    fn f_clear(mut self) -> Self {
+        self.clear();
+        self
+    }
+
+    fn f_insert(self, idx: usize, ch: char) -> Self {
+        self.f_try_insert(idx, ch)
+            .expect("f_insert: index out of bounds, not a char boundary, or capacity exceeded")
+    }
+
+    fn f_insert_str(self, idx: usize, string: &str) -> Self {
+        self.f_try_insert_str(idx, string)
+            .expect("f_insert_str: index out of bounds, not a char boundary, or capacity exceeded")
+    }
+
+    fn f_push(mut self, ch: char) -> Self {
+        self.push(ch).expect("f_push: capacity exceeded");
+        self
+    }
+
+    fn f_push_str(mut self, string: &str) -> Self {
+        self.push_str(string).expect("f_push_str: capacity exceeded");
+        self
+    }
+
+    fn f_replace_range<R>(self, range: R, replace_with: &str) -> Self
+    where
+        R: RangeBounds<usize>,
+    {
+        self.f_try_replace_range(range, replace_with)
+            .expect("f_replace_range: range out of bounds, not a char boundary, or capacity exceeded")
+    }
+
+    fn f_retain<F>(self, mut f: F) -> Self
+    where
+        F: FnMut(char) -> bool,
+    {
+        let mut result = Self::new();
+        for c in self.chars() {
+            if f(c) {
+                result.push(c).expect("f_retain: capacity exceeded");
+            }
+        }
+        result
+    }
+
+    fn f_truncate(mut self, new_len: usize) -> Self {
+        self.truncate(new_len);
+        self
+    }
+
+    fn f_try_insert(self, idx: usize, ch: char) -> Result<Self, FluentError> {
+        let mut buf = [0u8; 4];
+        self.f_try_insert_str(idx, ch.encode_utf8(&mut buf))
+    }
+
+    fn f_try_insert_str(self, idx: usize, string: &str) -> Result<Self, FluentError> {
+        let len = self.len();
+        if !is_inside_boundary(idx, len) {
+            return Err(FluentError::OutOfBounds { idx, len });
+        }
+        if !is_char_boundary(&self, idx) {
+            return Err(FluentError::NotCharBoundary { idx });
+        }
+        let (head, tail) = self.split_at(idx);
+        let mut result = Self::new();
+        push_str_checked(&mut result, head)?;
+        push_str_checked(&mut result, string)?;
+        push_str_checked(&mut result, tail)?;
+        Ok(result)
+    }
+
+    fn f_try_truncate(mut self, new_len: usize) -> Result<Self, FluentError> {
+        if new_len < self.len() && !is_char_boundary(&self, new_len) {
+            return Err(FluentError::NotCharBoundary { idx: new_len });
+        }
+        self.truncate(new_len);
+        Ok(self)
+    }
+
+    fn f_try_replace_range<R>(self, range: R, replace_with: &str) -> Result<Self, FluentError>
+    where
+        R: RangeBounds<usize>,
+    {
+        let (start, end) = resolve_range(&range, self.len())?;
+        if !is_char_boundary(&self, start) {
+            return Err(FluentError::NotCharBoundary { idx: start });
+        }
+        if !is_char_boundary(&self, end) {
+            return Err(FluentError::NotCharBoundary { idx: end });
+        }
+        let mut result = Self::new();
+        push_str_checked(&mut result, &self[..start])?;
+        push_str_checked(&mut result, replace_with)?;
+        push_str_checked(&mut result, &self[end..])?;
+        Ok(result)
+    }
+
+    fn f_push_fmt(mut self, args: core::fmt::Arguments<'_>) -> Self::FallibleOutput {
+        use core::fmt::Write as _;
+        self.write_fmt(args).map_err(|_| FluentError::Format)?;
+        Ok(self)
+    }
+
+    fn f_extend_chars<I>(self, iter: I) -> Self::FallibleOutput
+    where
+        I: IntoIterator<Item = char>,
+    {
+        let mut result = self;
+        for ch in iter {
+            let mut buf = [0u8; 4];
+            push_str_checked(&mut result, ch.encode_utf8(&mut buf))?;
+        }
+        Ok(result)
+    }
+
+    fn f_extend_str<'a, I>(self, iter: I) -> Self::FallibleOutput
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        let mut result = self;
+        for item in iter {
+            push_str_checked(&mut result, item)?;
+        }
+        Ok(result)
+    }
+
+    fn f_push_joined<'a, I>(self, sep: &str, iter: I) -> Self::FallibleOutput
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        let mut result = self;
+        let mut need_sep = !result.is_empty();
+        for item in iter {
+            if need_sep {
+                push_str_checked(&mut result, sep)?;
+            }
+            push_str_checked(&mut result, item)?;
+            need_sep = true;
+        }
+        Ok(result)
+    }
+}
+
+/// Pushes `text` onto `s`, translating a heapless capacity overflow
+/// into a [`FluentError::CapacityExceeded`].
+fn push_str_checked<const N: usize>(s: &mut HeaplessString<N>, text: &str) -> Result<(), FluentError> {
+    s.push_str(text).map_err(|()| FluentError::CapacityExceeded {
+        additional: text.len(),
+        capacity: s.capacity(),
+    })
+}
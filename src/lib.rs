@@ -2,8 +2,17 @@
 //! methods, allowing for a fluent construction of strings.
 //!
 //! It also implements two conditional push combinators
-//! `f_push_if` and `f_push_str_if` to help with such things
-//! as comma-separated token construction.
+//! `f_push_if` and `f_push_str_if`, and bulk combinators
+//! `f_extend_chars`, `f_extend_str` and `f_push_joined`, to help
+//! with such things as comma-separated token construction. A
+//! `f_push_fmt` combinator (with an `f_write!` macro wrapper) appends
+//! `core::fmt`-formatted output without an intermediate allocation.
+//!
+//! The `std` feature is enabled by default, providing
+//! implementations of [`FluentString`] for `String` and `&mut
+//! String`. Disable default features and enable `heapless` to use
+//! the same fluent API on fixed-capacity, `no_std` backends such as
+//! `heapless::String<N>`.
 //!
 //! # Examples
 //! ```
@@ -15,253 +24,41 @@
 //!     .f_truncate(33),
 //!     "my string is, maybe, a bit longer");
 //! ```
-use std::{collections::TryReserveError, ops::RangeBounds};
-pub trait FluentString: Sized {
-    /// As `String::clear` except returns `self`
-    #[must_use]
-    fn f_clear(self) -> Self;
-    /// As `String::insert` except returns `self`
-    #[must_use]
-    fn f_insert(self, idx: usize, ch: char) -> Self;
-    /// As `String::insert_str` except returns `self`
-    #[must_use]
-    fn f_insert_str(self, idx: usize, string: &str) -> Self;
-    /// As `String::push` except returns `self`
-    #[must_use]
-    fn f_push(self, ch: char) -> Self;
-    /// As `String::push_str` except returns `self`
-    #[must_use]
-    fn f_push_str(self, string: &str) -> Self;
-    /// As `String::replace_range` except returns `self`
-    #[must_use]
-    fn f_replace_range<R>(self, range: R, replace_with: &str) -> Self
-    where
-        R: RangeBounds<usize>;
-    /// As `String::reserve` except returns `self`
-    #[must_use]
-    fn f_reserve(self, additional: usize) -> Self;
-    /// As `String::reserve_exact` except returns `self`
-    #[must_use]
-    fn f_reserve_exact(self, additional: usize) -> Self;
-    /// As `String::retain` except returns `self`
-    #[must_use]
-    fn f_retain<F>(self, f: F) -> Self
-    where
-        F: FnMut(char) -> bool;
-    /// As `String::shrink_to` except returns `self`
-    #[must_use]
-    fn f_shrink_to(self, min_capacity: usize) -> Self;
-    /// As `String::shrink_to_fit` except returns `self`
-    #[must_use]
-    fn f_shrink_to_fit(self) -> Self;
-    /// As `String::truncate` except returns `self`
-    #[must_use]
-    fn f_truncate(self, new_len: usize) -> Self;
-    /// As `String::try_reserve` except returns `Result<Self, TryReserveError>`
-    /// # Errors
-    /// See `String::try_reserve_exact`
-    fn f_try_reserve(self, additional: usize) -> Result<Self, TryReserveError>;
-    /// As `String::try_reserve_exact` except returns `Result<Self, TryReserveError>`
-    /// # Errors
-    /// See `String::try_reserve_exact`
-    fn f_try_reserve_exact(self, additional: usize) -> Result<Self, TryReserveError>;
-
-    /// As `FluentString::f_push` except only if `f` returns true
-    #[must_use]
-    fn f_push_if<F>(self, ch: char, f: F) -> Self
-    where
-        F: Fn(&Self, char) -> bool,
-    {
-        if f(&self, ch) {
-            self.f_push(ch)
-        } else {
-            self
-        }
-    }
-
-    /// As `FluentString::f_push_str` except only if `f` returns true
-    #[must_use]
-    fn f_push_str_if<F>(self, string: &str, f: F) -> Self
-    where
-        F: Fn(&Self, &str) -> bool,
-    {
-        if f(&self, string) {
-            self.f_push_str(string)
-        } else {
-            self
-        }
-    }
-
-    /// As `FluentString::f_truncate` except only if `f` returns Some(usize)
-    #[must_use]
-    fn f_truncate_if<F>(self, f: F) -> Self
-    where
-        F: Fn(&Self) -> Option<usize>,
-    {
-        match f(&self) {
-            Some(l) => self.f_truncate(l),
-            None => self
-        } 
-    }
+#![cfg_attr(not(feature = "std"), no_std)]
+
+mod error;
+pub use error::{is_char_boundary, is_inside_boundary, FluentError};
+
+mod fluent;
+pub use fluent::FluentString;
+
+/// Calls [`FluentString::f_push_fmt`] with `format_args!($($arg)*)`.
+///
+/// ```
+/// use fluent_string::*;
+///
+/// let s = f_write!(String::new(), "{}:{}", "k", 1);
+/// assert_eq!(s, "k:1");
+/// ```
+#[macro_export]
+macro_rules! f_write {
+    ($dst:expr, $($arg:tt)*) => {
+        $crate::FluentString::f_push_fmt($dst, ::core::format_args!($($arg)*))
+    };
 }
 
-/// Fluent versions of all `std::string:String` mutation methods that
-/// otherwise return nothing.
-impl FluentString for String {
-    fn f_clear(mut self) -> Self {
-        self.clear();
-        self
-    }
-
-    fn f_insert(mut self, idx: usize, ch: char) -> Self {
-        self.insert(idx, ch);
-        self
-    }
-
-    fn f_insert_str(mut self, idx: usize, string: &str) -> Self {
-        self.insert_str(idx, string);
-        self
-    }
-
-    fn f_push(mut self, ch: char) -> Self {
-        self.push(ch);
-        self
-    }
-
-    fn f_push_str(mut self, string: &str) -> Self {
-        self.push_str(string);
-        self
-    }
-
-    fn f_replace_range<R>(mut self, range: R, replace_with: &str) -> Self
-    where
-        R: RangeBounds<usize>,
-    {
-        self.replace_range(range, replace_with);
-        self
-    }
-
-    fn f_reserve(mut self, additional: usize) -> Self {
-        self.reserve(additional);
-        self
-    }
+#[cfg(feature = "std")]
+mod reserve;
+#[cfg(feature = "std")]
+pub use reserve::FluentStringReserve;
 
-    fn f_reserve_exact(mut self, additional: usize) -> Self {
-        self.reserve_exact(additional);
-        self
-    }
-
-    fn f_retain<F>(mut self, f: F) -> Self
-    where
-        F: FnMut(char) -> bool,
-    {
-        self.retain(f);
-        self
-    }
-
-    fn f_shrink_to(mut self, min_capacity: usize) -> Self {
-        self.shrink_to(min_capacity);
-        self
-    }
-
-    fn f_shrink_to_fit(mut self) -> Self {
-        self.shrink_to_fit();
-        self
-    }
-
-    fn f_truncate(mut self, new_len: usize) -> Self {
-        self.truncate(new_len);
-        self
-    }
-
-    fn f_try_reserve(mut self, additional: usize) -> Result<Self, TryReserveError> {
-        self.try_reserve(additional).map(|()| self)
-    }
-
-    fn f_try_reserve_exact(mut self, additional: usize) -> Result<Self, TryReserveError> {
-        self.try_reserve_exact(additional).map(|()| self)
-    }
-}
+#[cfg(feature = "std")]
+mod std_impl;
 
-/// Fluent versions of all `&mut std::string:String` mutation methods that
-/// otherwise return nothing.
-impl FluentString for &mut String {
-    fn f_clear(self) -> Self {
-        self.clear();
-        self
-    }
-
-    fn f_insert(self, idx: usize, ch: char) -> Self {
-        self.insert(idx, ch);
-        self
-    }
-
-    fn f_insert_str(self, idx: usize, string: &str) -> Self {
-        self.insert_str(idx, string);
-        self
-    }
-
-    fn f_push(self, ch: char) -> Self {
-        self.push(ch);
-        self
-    }
-
-    fn f_push_str(self, string: &str) -> Self {
-        self.push_str(string);
-        self
-    }
-
-    fn f_replace_range<R>(self, range: R, replace_with: &str) -> Self
-    where
-        R: RangeBounds<usize>,
-    {
-        self.replace_range(range, replace_with);
-        self
-    }
-
-    fn f_reserve(self, additional: usize) -> Self {
-        self.reserve(additional);
-        self
-    }
-
-    fn f_reserve_exact(self, additional: usize) -> Self {
-        self.reserve_exact(additional);
-        self
-    }
-
-    fn f_retain<F>(self, f: F) -> Self
-    where
-        F: FnMut(char) -> bool,
-    {
-        self.retain(f);
-        self
-    }
-
-    fn f_shrink_to(self, min_capacity: usize) -> Self {
-        self.shrink_to(min_capacity);
-        self
-    }
-
-    fn f_shrink_to_fit(self) -> Self {
-        self.shrink_to_fit();
-        self
-    }
-
-    fn f_truncate(self, new_len: usize) -> Self {
-        self.truncate(new_len);
-        self
-    }
-
-    fn f_try_reserve(self, additional: usize) -> Result<Self, TryReserveError> {
-        self.try_reserve(additional).map(|()| self)
-    }
-
-    fn f_try_reserve_exact(self, additional: usize) -> Result<Self, TryReserveError> {
-        self.try_reserve_exact(additional).map(|()| self)
-    }
-}
+#[cfg(feature = "heapless")]
+mod heapless_impl;
 
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 mod tests {
     use super::*;
 
@@ -429,6 +226,77 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_owned_try_insert_ok() {
+        assert!("this is a string"
+            .to_string()
+            .f_try_insert(5, 'b')
+            .unwrap()
+            .eq_ignore_ascii_case("THIS BIS A STRING"));
+    }
+    #[test]
+    fn test_owned_try_insert_not_char_boundary() {
+        assert_eq!(
+            "héllo".to_string().f_try_insert(2, 'x'),
+            Err(FluentError::NotCharBoundary { idx: 2 })
+        );
+    }
+    #[test]
+    fn test_owned_try_insert_str_out_of_bounds() {
+        assert_eq!(
+            "hey".to_string().f_try_insert_str(10, "!"),
+            Err(FluentError::OutOfBounds { idx: 10, len: 3 })
+        );
+    }
+    #[test]
+    fn test_owned_try_truncate_ok() {
+        assert_eq!(
+            "this is a string".to_string().f_try_truncate(4).unwrap(),
+            "this"
+        );
+    }
+    #[test]
+    fn test_owned_try_truncate_not_char_boundary() {
+        assert_eq!(
+            "héllo".to_string().f_try_truncate(2),
+            Err(FluentError::NotCharBoundary { idx: 2 })
+        );
+    }
+    #[test]
+    fn test_owned_try_replace_range_ok() {
+        assert_eq!(
+            "this is a string"
+                .to_string()
+                .f_try_replace_range(7..9, " not your")
+                .unwrap(),
+            "this is not your string"
+        );
+    }
+    #[test]
+    fn test_owned_try_replace_range_out_of_bounds() {
+        assert_eq!(
+            "hey".to_string().f_try_replace_range(0..10, "!"),
+            Err(FluentError::OutOfBounds { idx: 10, len: 3 })
+        );
+    }
+    #[test]
+    fn test_owned_try_replace_range_inclusive_end_overflow_does_not_panic() {
+        assert_eq!(
+            "hey".to_string().f_try_replace_range(0..=usize::MAX, "!"),
+            Err(FluentError::OutOfBounds { idx: 4, len: 3 })
+        );
+    }
+    #[test]
+    fn test_owned_try_replace_range_inverted() {
+        use std::ops::Bound;
+        assert_eq!(
+            "hey"
+                .to_string()
+                .f_try_replace_range((Bound::Included(2), Bound::Included(0)), "!"),
+            Err(FluentError::InvalidRange { start: 2, end: 1 })
+        );
+    }
+
     // String ref tests
     #[test]
     fn test_ref_clear() {
@@ -593,4 +461,228 @@ mod tests {
             "hey you"
         );
     }
+
+    #[test]
+    fn test_ref_try_insert_ok() {
+        let mut s = "this is a string".to_string();
+        let s = &mut s;
+        assert!(s
+            .f_try_insert(5, 'b')
+            .unwrap()
+            .eq_ignore_ascii_case("THIS BIS A STRING"));
+    }
+
+    #[test]
+    fn test_ref_try_truncate_not_char_boundary() {
+        let mut s = "héllo".to_string();
+        let s = &mut s;
+        assert_eq!(s.f_try_truncate(2), Err(FluentError::NotCharBoundary { idx: 2 }));
+    }
+
+    #[test]
+    fn test_ref_try_replace_range_ok() {
+        let mut s = "this is a string".to_string();
+        let s = &mut s;
+        assert_eq!(
+            s.f_try_replace_range(7..9, " not your").unwrap(),
+            "this is not your string"
+        );
+    }
+
+    #[test]
+    fn test_owned_push_fmt() {
+        assert_eq!(
+            "k".to_string().f_push_fmt(format_args!(":{}", 1)),
+            "k:1"
+        );
+    }
+
+    #[test]
+    fn test_owned_f_write_macro() {
+        assert_eq!(f_write!(String::new(), "{}:{}", "k", 1), "k:1");
+    }
+
+    #[test]
+    fn test_ref_push_fmt() {
+        let mut s = "k".to_string();
+        let s = &mut s;
+        assert_eq!(s.f_push_fmt(format_args!(":{}", 1)), "k:1");
+    }
+
+    #[test]
+    fn test_owned_extend_chars() {
+        assert_eq!("hey".to_string().f_extend_chars(['!', '?']), "hey!?");
+    }
+
+    #[test]
+    fn test_owned_extend_str() {
+        assert_eq!("hey".to_string().f_extend_str(["!", "?"]), "hey!?");
+    }
+
+    #[test]
+    fn test_owned_push_joined_empty_self() {
+        assert_eq!(
+            String::new().f_push_joined(",", ["a", "b", "c"]),
+            "a,b,c"
+        );
+    }
+
+    #[test]
+    fn test_owned_push_joined_non_empty_self() {
+        assert_eq!(
+            "start".to_string().f_push_joined(",", ["a", "b"]),
+            "start,a,b"
+        );
+    }
+
+    #[test]
+    fn test_ref_extend_chars() {
+        let mut s = "hey".to_string();
+        let s = &mut s;
+        assert_eq!(s.f_extend_chars(['!', '?']), "hey!?");
+    }
+
+    #[test]
+    fn test_ref_push_joined_non_empty_self() {
+        let mut s = "start".to_string();
+        let s = &mut s;
+        assert_eq!(s.f_push_joined(",", ["a", "b"]), "start,a,b");
+    }
+}
+
+#[cfg(all(test, feature = "heapless"))]
+mod heapless_tests {
+    use super::*;
+    use heapless::String as HeaplessString;
+
+    #[test]
+    fn test_insert() {
+        let s: HeaplessString<20> = HeaplessString::try_from("this is a string").unwrap();
+        assert!(s
+            .f_insert(5, 'b')
+            .eq_ignore_ascii_case("THIS BIS A STRING"));
+    }
+
+    #[test]
+    fn test_retain() {
+        let s: HeaplessString<16> = HeaplessString::try_from("this is a string").unwrap();
+        assert!(s
+            .f_retain(|c| c != 't')
+            .eq_ignore_ascii_case("HIS IS A SRING"));
+    }
+
+    #[test]
+    fn test_try_insert_str_out_of_bounds() {
+        let s: HeaplessString<8> = HeaplessString::try_from("hey").unwrap();
+        assert_eq!(
+            s.f_try_insert_str(10, "!"),
+            Err(FluentError::OutOfBounds { idx: 10, len: 3 })
+        );
+    }
+
+    #[test]
+    fn test_try_insert_not_char_boundary() {
+        let s: HeaplessString<16> = HeaplessString::try_from("héllo").unwrap();
+        assert_eq!(
+            s.f_try_insert(2, 'x'),
+            Err(FluentError::NotCharBoundary { idx: 2 })
+        );
+    }
+
+    #[test]
+    fn test_try_truncate_not_char_boundary() {
+        let s: HeaplessString<16> = HeaplessString::try_from("héllo").unwrap();
+        assert_eq!(
+            s.f_try_truncate(2),
+            Err(FluentError::NotCharBoundary { idx: 2 })
+        );
+    }
+
+    #[test]
+    fn test_try_replace_range_ok() {
+        let s: HeaplessString<32> = HeaplessString::try_from("this is a string").unwrap();
+        assert_eq!(
+            s.f_try_replace_range(7..9, " not your").unwrap(),
+            "this is not your string"
+        );
+    }
+
+    #[test]
+    fn test_try_replace_range_out_of_bounds() {
+        let s: HeaplessString<8> = HeaplessString::try_from("hey").unwrap();
+        assert_eq!(
+            s.f_try_replace_range(0..10, "!"),
+            Err(FluentError::OutOfBounds { idx: 10, len: 3 })
+        );
+    }
+
+    #[test]
+    fn test_push_fmt() {
+        let s: HeaplessString<8> = HeaplessString::try_from("k").unwrap();
+        assert_eq!(s.f_push_fmt(format_args!(":{}", 1)).unwrap(), "k:1");
+    }
+
+    #[test]
+    fn test_extend_chars() {
+        let s: HeaplessString<8> = HeaplessString::try_from("hey").unwrap();
+        assert_eq!(s.f_extend_chars(['!', '?']).unwrap(), "hey!?");
+    }
+
+    #[test]
+    fn test_extend_str() {
+        let s: HeaplessString<8> = HeaplessString::try_from("hey").unwrap();
+        assert_eq!(s.f_extend_str(["!", "?"]).unwrap(), "hey!?");
+    }
+
+    #[test]
+    fn test_push_joined() {
+        let s: HeaplessString<8> = HeaplessString::new();
+        assert_eq!(
+            s.f_push_joined(",", ["a", "b", "c"]).unwrap(),
+            "a,b,c"
+        );
+    }
+
+    #[test]
+    fn test_try_insert_str_capacity_exceeded() {
+        // Capacity 4 ("hey" is 3 bytes); inserting "!!" pushes it to 5.
+        let s: HeaplessString<4> = HeaplessString::try_from("hey").unwrap();
+        assert_eq!(
+            s.f_try_insert_str(3, "!!"),
+            Err(FluentError::CapacityExceeded { additional: 2, capacity: 4 })
+        );
+    }
+
+    #[test]
+    fn test_extend_str_stops_cleanly_on_mid_iteration_overflow() {
+        // Capacity 5: "ab" + "cd" fits (4 bytes, one spare), so "ef" is
+        // the one that overflows and the iteration short-circuits
+        // there instead of silently truncating.
+        let s: HeaplessString<5> = HeaplessString::try_from("ab").unwrap();
+        assert_eq!(
+            s.f_extend_str(["cd", "ef"]),
+            Err(FluentError::CapacityExceeded { additional: 2, capacity: 5 })
+        );
+    }
+
+    #[test]
+    fn test_extend_chars_stops_cleanly_on_mid_iteration_overflow() {
+        let s: HeaplessString<4> = HeaplessString::try_from("abc").unwrap();
+        assert_eq!(
+            s.f_extend_chars(['d', 'e']),
+            Err(FluentError::CapacityExceeded { additional: 1, capacity: 4 })
+        );
+    }
+
+    #[test]
+    fn test_push_joined_stops_cleanly_on_mid_iteration_overflow() {
+        // Capacity 3: "a" + "," + "b" fills it exactly, so the second
+        // item's separator is what overflows, and "cc" is never
+        // reached.
+        let s: HeaplessString<3> = HeaplessString::try_from("a").unwrap();
+        assert_eq!(
+            s.f_push_joined(",", ["b", "cc"]),
+            Err(FluentError::CapacityExceeded { additional: 1, capacity: 3 })
+        );
+    }
 }
@@ -0,0 +1,32 @@
+//! Capacity-management combinators that only make sense for
+//! heap-backed, growable string types.
+use std::collections::TryReserveError;
+
+/// Fluent versions of the `String` capacity-management methods.
+///
+/// These don't apply to fixed-capacity backends (there's nothing to
+/// reserve or shrink), so they live in their own trait rather than on
+/// [`FluentString`](crate::FluentString), which is implemented for
+/// those backends too.
+pub trait FluentStringReserve: Sized {
+    /// As `String::reserve` except returns `self`
+    #[must_use]
+    fn f_reserve(self, additional: usize) -> Self;
+    /// As `String::reserve_exact` except returns `self`
+    #[must_use]
+    fn f_reserve_exact(self, additional: usize) -> Self;
+    /// As `String::shrink_to` except returns `self`
+    #[must_use]
+    fn f_shrink_to(self, min_capacity: usize) -> Self;
+    /// As `String::shrink_to_fit` except returns `self`
+    #[must_use]
+    fn f_shrink_to_fit(self) -> Self;
+    /// As `String::try_reserve` except returns `Result<Self, TryReserveError>`
+    /// # Errors
+    /// See `String::try_reserve`
+    fn f_try_reserve(self, additional: usize) -> Result<Self, TryReserveError>;
+    /// As `String::try_reserve_exact` except returns `Result<Self, TryReserveError>`
+    /// # Errors
+    /// See `String::try_reserve_exact`
+    fn f_try_reserve_exact(self, additional: usize) -> Result<Self, TryReserveError>;
+}
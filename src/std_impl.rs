@@ -0,0 +1,328 @@
+//! `std`-only implementations of [`FluentString`] and
+//! [`FluentStringReserve`] for heap-backed `String`.
+use crate::error::resolve_range;
+use crate::{is_char_boundary, is_inside_boundary, FluentError, FluentString, FluentStringReserve};
+use std::collections::TryReserveError;
+use std::ops::RangeBounds;
+
+/// Fluent versions of all `std::string::String` mutation methods that
+/// otherwise return nothing.
+impl FluentString for String {
+    type FallibleOutput = Self;
+
+    fn f_clear(mut self) -> Self {
+        self.clear();
+        self
+    }
+
+    fn f_insert(mut self, idx: usize, ch: char) -> Self {
+        self.insert(idx, ch);
+        self
+    }
+
+    fn f_insert_str(mut self, idx: usize, string: &str) -> Self {
+        self.insert_str(idx, string);
+        self
+    }
+
+    fn f_push(mut self, ch: char) -> Self {
+        self.push(ch);
+        self
+    }
+
+    fn f_push_str(mut self, string: &str) -> Self {
+        self.push_str(string);
+        self
+    }
+
+    fn f_replace_range<R>(mut self, range: R, replace_with: &str) -> Self
+    where
+        R: RangeBounds<usize>,
+    {
+        self.replace_range(range, replace_with);
+        self
+    }
+
+    fn f_retain<F>(mut self, f: F) -> Self
+    where
+        F: FnMut(char) -> bool,
+    {
+        self.retain(f);
+        self
+    }
+
+    fn f_truncate(mut self, new_len: usize) -> Self {
+        self.truncate(new_len);
+        self
+    }
+
+    fn f_try_insert(self, idx: usize, ch: char) -> Result<Self, FluentError> {
+        if !is_inside_boundary(idx, self.len()) {
+            return Err(FluentError::OutOfBounds { idx, len: self.len() });
+        }
+        if !is_char_boundary(&self, idx) {
+            return Err(FluentError::NotCharBoundary { idx });
+        }
+        Ok(self.f_insert(idx, ch))
+    }
+
+    fn f_try_insert_str(self, idx: usize, string: &str) -> Result<Self, FluentError> {
+        if !is_inside_boundary(idx, self.len()) {
+            return Err(FluentError::OutOfBounds { idx, len: self.len() });
+        }
+        if !is_char_boundary(&self, idx) {
+            return Err(FluentError::NotCharBoundary { idx });
+        }
+        Ok(self.f_insert_str(idx, string))
+    }
+
+    fn f_try_truncate(self, new_len: usize) -> Result<Self, FluentError> {
+        if new_len < self.len() && !is_char_boundary(&self, new_len) {
+            return Err(FluentError::NotCharBoundary { idx: new_len });
+        }
+        Ok(self.f_truncate(new_len))
+    }
+
+    fn f_try_replace_range<R>(self, range: R, replace_with: &str) -> Result<Self, FluentError>
+    where
+        R: RangeBounds<usize>,
+    {
+        let (start, end) = resolve_range(&range, self.len())?;
+        if !is_char_boundary(&self, start) {
+            return Err(FluentError::NotCharBoundary { idx: start });
+        }
+        if !is_char_boundary(&self, end) {
+            return Err(FluentError::NotCharBoundary { idx: end });
+        }
+        Ok(self.f_replace_range(range, replace_with))
+    }
+
+    fn f_push_fmt(mut self, args: std::fmt::Arguments<'_>) -> Self {
+        use std::fmt::Write as _;
+        self.write_fmt(args)
+            .expect("a Display implementation returned an error");
+        self
+    }
+
+    fn f_extend_chars<I>(mut self, iter: I) -> Self
+    where
+        I: IntoIterator<Item = char>,
+    {
+        self.extend(iter);
+        self
+    }
+
+    fn f_extend_str<'a, I>(mut self, iter: I) -> Self
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        self.extend(iter);
+        self
+    }
+
+    fn f_push_joined<'a, I>(mut self, sep: &str, iter: I) -> Self
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        let mut need_sep = !self.is_empty();
+        for item in iter {
+            if need_sep {
+                self.push_str(sep);
+            }
+            self.push_str(item);
+            need_sep = true;
+        }
+        self
+    }
+}
+
+impl FluentStringReserve for String {
+    fn f_reserve(mut self, additional: usize) -> Self {
+        self.reserve(additional);
+        self
+    }
+
+    fn f_reserve_exact(mut self, additional: usize) -> Self {
+        self.reserve_exact(additional);
+        self
+    }
+
+    fn f_shrink_to(mut self, min_capacity: usize) -> Self {
+        self.shrink_to(min_capacity);
+        self
+    }
+
+    fn f_shrink_to_fit(mut self) -> Self {
+        self.shrink_to_fit();
+        self
+    }
+
+    fn f_try_reserve(mut self, additional: usize) -> Result<Self, TryReserveError> {
+        self.try_reserve(additional).map(|()| self)
+    }
+
+    fn f_try_reserve_exact(mut self, additional: usize) -> Result<Self, TryReserveError> {
+        self.try_reserve_exact(additional).map(|()| self)
+    }
+}
+
+/// Fluent versions of all `&mut std::string::String` mutation methods
+/// that otherwise return nothing.
+impl FluentString for &mut String {
+    type FallibleOutput = Self;
+
+    fn f_clear(self) -> Self {
+        self.clear();
+        self
+    }
+
+    fn f_insert(self, idx: usize, ch: char) -> Self {
+        self.insert(idx, ch);
+        self
+    }
+
+    fn f_insert_str(self, idx: usize, string: &str) -> Self {
+        self.insert_str(idx, string);
+        self
+    }
+
+    fn f_push(self, ch: char) -> Self {
+        self.push(ch);
+        self
+    }
+
+    fn f_push_str(self, string: &str) -> Self {
+        self.push_str(string);
+        self
+    }
+
+    fn f_replace_range<R>(self, range: R, replace_with: &str) -> Self
+    where
+        R: RangeBounds<usize>,
+    {
+        self.replace_range(range, replace_with);
+        self
+    }
+
+    fn f_retain<F>(self, f: F) -> Self
+    where
+        F: FnMut(char) -> bool,
+    {
+        self.retain(f);
+        self
+    }
+
+    fn f_truncate(self, new_len: usize) -> Self {
+        self.truncate(new_len);
+        self
+    }
+
+    fn f_try_insert(self, idx: usize, ch: char) -> Result<Self, FluentError> {
+        if !is_inside_boundary(idx, self.len()) {
+            return Err(FluentError::OutOfBounds { idx, len: self.len() });
+        }
+        if !is_char_boundary(self, idx) {
+            return Err(FluentError::NotCharBoundary { idx });
+        }
+        Ok(self.f_insert(idx, ch))
+    }
+
+    fn f_try_insert_str(self, idx: usize, string: &str) -> Result<Self, FluentError> {
+        if !is_inside_boundary(idx, self.len()) {
+            return Err(FluentError::OutOfBounds { idx, len: self.len() });
+        }
+        if !is_char_boundary(self, idx) {
+            return Err(FluentError::NotCharBoundary { idx });
+        }
+        Ok(self.f_insert_str(idx, string))
+    }
+
+    fn f_try_truncate(self, new_len: usize) -> Result<Self, FluentError> {
+        if new_len < self.len() && !is_char_boundary(self, new_len) {
+            return Err(FluentError::NotCharBoundary { idx: new_len });
+        }
+        Ok(self.f_truncate(new_len))
+    }
+
+    fn f_try_replace_range<R>(self, range: R, replace_with: &str) -> Result<Self, FluentError>
+    where
+        R: RangeBounds<usize>,
+    {
+        let (start, end) = resolve_range(&range, self.len())?;
+        if !is_char_boundary(self, start) {
+            return Err(FluentError::NotCharBoundary { idx: start });
+        }
+        if !is_char_boundary(self, end) {
+            return Err(FluentError::NotCharBoundary { idx: end });
+        }
+        Ok(self.f_replace_range(range, replace_with))
+    }
+
+    fn f_push_fmt(self, args: std::fmt::Arguments<'_>) -> Self {
+        use std::fmt::Write as _;
+        self.write_fmt(args)
+            .expect("a Display implementation returned an error");
+        self
+    }
+
+    fn f_extend_chars<I>(self, iter: I) -> Self
+    where
+        I: IntoIterator<Item = char>,
+    {
+        self.extend(iter);
+        self
+    }
+
+    fn f_extend_str<'a, I>(self, iter: I) -> Self
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        self.extend(iter);
+        self
+    }
+
+    fn f_push_joined<'a, I>(self, sep: &str, iter: I) -> Self
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        let mut need_sep = !self.is_empty();
+        for item in iter {
+            if need_sep {
+                self.push_str(sep);
+            }
+            self.push_str(item);
+            need_sep = true;
+        }
+        self
+    }
+}
+
+impl FluentStringReserve for &mut String {
+    fn f_reserve(self, additional: usize) -> Self {
+        self.reserve(additional);
+        self
+    }
+
+    fn f_reserve_exact(self, additional: usize) -> Self {
+        self.reserve_exact(additional);
+        self
+    }
+
+    fn f_shrink_to(self, min_capacity: usize) -> Self {
+        self.shrink_to(min_capacity);
+        self
+    }
+
+    fn f_shrink_to_fit(self) -> Self {
+        self.shrink_to_fit();
+        self
+    }
+
+    fn f_try_reserve(self, additional: usize) -> Result<Self, TryReserveError> {
+        self.try_reserve(additional).map(|()| self)
+    }
+
+    fn f_try_reserve_exact(self, additional: usize) -> Result<Self, TryReserveError> {
+        self.try_reserve_exact(additional).map(|()| self)
+    }
+}